@@ -0,0 +1,115 @@
+//! An interactive REPL that infers and prints the type of each statement.
+//!
+//! Variables declared on earlier lines stay in scope for later input, multi-line
+//! input continues while brackets are unbalanced, and a couple of `:`-prefixed
+//! commands expose the engine's state.
+
+use rustyline::error::ReadlineError;
+use rustyline::DefaultEditor;
+use type_inference::*;
+
+fn main() -> rustyline::Result<()> {
+    let mut editor = DefaultEditor::new()?;
+    let mut infer = Infer::default();
+    let mut env = Env::default();
+
+    while let Some(line) = read_statement(&mut editor) {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        editor.add_history_entry(line).ok();
+
+        if line == ":env" {
+            for (name, scheme) in env.iter() {
+                println!("{name} : {}", scheme.ty);
+            }
+        } else if let Some(rest) = line.strip_prefix(":type") {
+            show_type(rest.trim(), &infer, &env);
+        } else {
+            run(line, &mut infer, &mut env);
+        }
+    }
+
+    Ok(())
+}
+
+/// Read a single statement, continuing the prompt while `[ ] { }` are unbalanced.
+fn read_statement(editor: &mut DefaultEditor) -> Option<String> {
+    let mut buffer = match editor.readline("> ") {
+        Ok(line) => line,
+        Err(ReadlineError::Interrupted | ReadlineError::Eof) => return None,
+        Err(err) => {
+            eprintln!("{err}");
+            return None;
+        }
+    };
+    while !buffer.trim().is_empty() && !is_balanced(&buffer) {
+        match editor.readline("... ") {
+            Ok(line) => {
+                buffer.push('\n');
+                buffer.push_str(&line);
+            }
+            Err(_) => break,
+        }
+    }
+    Some(buffer)
+}
+
+/// Whether `input` opens no more `[`/`{` than it closes with `]`/`}`; brackets
+/// are counted, not paired.
+fn is_balanced(input: &str) -> bool {
+    let mut depth = 0i32;
+    for c in input.chars() {
+        match c {
+            '[' | '{' => depth += 1,
+            ']' | '}' => depth -= 1,
+            _ => {}
+        }
+    }
+    depth <= 0
+}
+
+/// Parse, infer, and bind each statement, printing the inferred type.
+fn run(input: &str, infer: &mut Infer, env: &mut Env) {
+    let exprs = match Parser::new(input).parse() {
+        Ok(exprs) => exprs,
+        Err(err) => {
+            eprintln!("{err}");
+            return;
+        }
+    };
+    for expr in &exprs {
+        let ty = match infer.infer(env, expr) {
+            Ok(ty) => infer.apply(&ty),
+            Err(err) => {
+                eprintln!("type error: {err:?}");
+                return;
+            }
+        };
+        match expr {
+            // A declaration reports its name alongside its inferred type.
+            Expr::Var(name, _, _) => println!("{name} : {ty}"),
+            _ => println!("{ty}"),
+        }
+    }
+}
+
+/// Infer the type of an expression without binding it into the environment.
+fn show_type(input: &str, infer: &Infer, env: &Env) {
+    let exprs = match Parser::new(input).parse() {
+        Ok(exprs) => exprs,
+        Err(err) => {
+            eprintln!("{err}");
+            return;
+        }
+    };
+    let mut infer = infer.clone();
+    let mut env = env.clone();
+    for expr in &exprs {
+        match infer.infer(&mut env, expr) {
+            Ok(ty) => println!("{}", infer.apply(&ty)),
+            Err(err) => eprintln!("type error: {err:?}"),
+        }
+    }
+}
@@ -16,6 +16,43 @@ pub enum Type {
     String,
     List(Vec<Type>),
     Map(Vec<Type>, Vec<Type>),
+    /// A function from its parameter types to its return type.
+    Fn(Vec<Type>, Box<Type>),
+    /// A unification variable produced by the inference engine; carries a unique
+    /// id that a [`Subst`] may later resolve to a concrete type.
+    Var(u32),
+}
+
+impl std::fmt::Display for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Type::Bool => write!(f, "bool"),
+            Type::Integer => write!(f, "i64"),
+            Type::String => write!(f, "str"),
+            Type::List(types) => write!(f, "list[{}]", render_union(types)),
+            Type::Map(keys, vals) => {
+                write!(f, "map[{}, {}]", render_union(keys), render_union(vals))
+            }
+            Type::Fn(params, ret) => {
+                let params = params
+                    .iter()
+                    .map(|t| t.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(f, "fn({params}) -> {ret}")
+            }
+            Type::Var(n) => write!(f, "t{n}"),
+        }
+    }
+}
+
+/// Render a union of types as `a | b | c`.
+pub fn render_union(types: &[Type]) -> String {
+    types
+        .iter()
+        .map(|t| t.to_string())
+        .collect::<Vec<_>>()
+        .join(" | ")
 }
 
 impl std::hash::Hash for Value {
@@ -147,7 +184,47 @@ impl From<Vec<Value>> for Value {
 pub enum Expr {
     Var(String, Vec<Type>, Box<Expr>),
     Value(Value),
-    If(Box<Expr>, Box<Expr>),
+    /// `if <cond> { <then> } else { <else> }`; the condition must infer to
+    /// `Type::Bool`, and the type is the union of the two branches.
+    If(Box<Expr>, Box<Expr>, Box<Expr>),
+    /// A reference to a previously bound variable, resolved against the
+    /// inference [`Env`].
+    Ref(String),
+    /// A lambda: named parameters (each with a declared type union) and a body.
+    Lam {
+        params: Vec<(String, Vec<Type>)>,
+        body: Box<Expr>,
+    },
+    /// The application of `callee` to `args`.
+    Call {
+        callee: Box<Expr>,
+        args: Vec<Expr>,
+    },
+    /// A binary operation over two operands.
+    BinOp {
+        op: Op,
+        lhs: Box<Expr>,
+        rhs: Box<Expr>,
+    },
+}
+
+/// The binary operators the language understands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Eq,
+    Lt,
+}
+
+/// Binding power of an operator; higher binds tighter.
+fn op_prec(op: &Op) -> u8 {
+    match op {
+        Op::Eq | Op::Lt => 1,
+        Op::Add | Op::Sub => 2,
+        Op::Mul => 3,
+    }
 }
 
 impl From<Value> for Expr {
@@ -163,6 +240,47 @@ impl PartialEq for Expr {
                 self_name == other_name && self_types == other_types
             }
             (Expr::Value(self_val), Expr::Value(other_val)) => self_val == other_val,
+            (Expr::Ref(self_name), Expr::Ref(other_name)) => self_name == other_name,
+            (
+                Expr::If(self_cond, self_then, self_else),
+                Expr::If(other_cond, other_then, other_else),
+            ) => {
+                self_cond == other_cond
+                    && self_then == other_then
+                    && self_else == other_else
+            }
+            (
+                Expr::Lam {
+                    params: self_params,
+                    body: self_body,
+                },
+                Expr::Lam {
+                    params: other_params,
+                    body: other_body,
+                },
+            ) => self_params == other_params && self_body == other_body,
+            (
+                Expr::Call {
+                    callee: self_callee,
+                    args: self_args,
+                },
+                Expr::Call {
+                    callee: other_callee,
+                    args: other_args,
+                },
+            ) => self_callee == other_callee && self_args == other_args,
+            (
+                Expr::BinOp {
+                    op: self_op,
+                    lhs: self_lhs,
+                    rhs: self_rhs,
+                },
+                Expr::BinOp {
+                    op: other_op,
+                    lhs: other_lhs,
+                    rhs: other_rhs,
+                },
+            ) => self_op == other_op && self_lhs == other_lhs && self_rhs == other_rhs,
             _ => false,
         }
     }
@@ -174,15 +292,429 @@ impl Expr {
         match self {
             Expr::Var(_, types, _) => types.to_vec(),
             Expr::Value(value) => vec![value.type_of()],
-            Expr::If(left, right) => {
-                let mut left = left.type_of();
-                left.extend(right.type_of());
-                left
+            Expr::If(_, then, otherwise) => {
+                let mut then = then.type_of();
+                then.extend(otherwise.type_of());
+                then
+            }
+            // A bare reference has no type on its own; the inference engine
+            // resolves it against the surrounding `Env`.
+            Expr::Ref(_) => vec![],
+            Expr::Lam { params, body } => {
+                let param_types = params
+                    .iter()
+                    .map(|(_, types)| types.first().cloned().unwrap_or(Type::Bool))
+                    .collect();
+                let ret = body.type_of().first().cloned().unwrap_or(Type::Bool);
+                vec![Type::Fn(param_types, Box::new(ret))]
+            }
+            Expr::Call { callee, .. } => match callee.type_of().first() {
+                Some(Type::Fn(_, ret)) => vec![(**ret).clone()],
+                _ => vec![],
+            },
+            Expr::BinOp { op, lhs, .. } => match op {
+                Op::Eq | Op::Lt => vec![Type::Bool],
+                Op::Add | Op::Sub | Op::Mul => lhs.type_of(),
+            },
+        }
+    }
+}
+
+/// A mismatch between a variable's declared union and the type inferred for its
+/// value. `found` is the offending inferred type, `expected` is the declared
+/// union it failed to satisfy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeError {
+    pub name: String,
+    pub found: Type,
+    pub expected: Vec<Type>,
+}
+
+/// Verify that every `let`-binding's value conforms to its declared union.
+///
+/// For each `Expr::Var(name, declared, value)` the value's inferred type must be
+/// a member of `declared`; for `List`/`Map` values every element/entry type must
+/// be covered by the declared component unions. The parsed `Vec<Expr>` is
+/// returned unchanged on success, otherwise every mismatch is collected.
+pub fn check(exprs: Vec<Expr>) -> Result<Vec<Expr>, Vec<TypeError>> {
+    let mut errors = vec![];
+    for expr in &exprs {
+        if let Expr::Var(name, declared, value) = expr {
+            for found in value.type_of() {
+                if !conforms_to_union(&found, declared) {
+                    errors.push(TypeError {
+                        name: name.clone(),
+                        found,
+                        expected: declared.clone(),
+                    });
+                }
+            }
+        }
+    }
+    if errors.is_empty() {
+        Ok(exprs)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Whether `found` is a member of the declared union `declared`.
+fn conforms_to_union(found: &Type, declared: &[Type]) -> bool {
+    declared.iter().any(|d| conforms_to(found, d))
+}
+
+/// Whether the inferred type `found` is covered by the single declared type
+/// `declared`, recursing into `List`/`Map` component unions.
+fn conforms_to(found: &Type, declared: &Type) -> bool {
+    match (found, declared) {
+        (Type::Bool, Type::Bool)
+        | (Type::Integer, Type::Integer)
+        | (Type::String, Type::String) => true,
+        (Type::List(found), Type::List(declared)) => found
+            .iter()
+            .all(|f| conforms_to_union(f, declared)),
+        (Type::Map(found_keys, found_vals), Type::Map(declared_keys, declared_vals)) => {
+            found_keys.iter().all(|f| conforms_to_union(f, declared_keys))
+                && found_vals.iter().all(|f| conforms_to_union(f, declared_vals))
+        }
+        _ => false,
+    }
+}
+
+/// A mapping from unification-variable ids to the types they resolve to.
+pub type Subst = HashMap<u32, Type>;
+
+/// A (possibly polymorphic) type scheme: `ty` quantified over `vars`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scheme {
+    pub vars: Vec<u32>,
+    pub ty: Type,
+}
+
+/// The typing environment: the schemes currently in scope by name.
+#[derive(Debug, Clone, Default)]
+pub struct Env {
+    bindings: HashMap<String, Scheme>,
+}
+
+impl Env {
+    pub fn get(&self, name: &str) -> Option<&Scheme> {
+        self.bindings.get(name)
+    }
+
+    pub fn insert(&mut self, name: String, scheme: Scheme) {
+        self.bindings.insert(name, scheme);
+    }
+
+    /// Iterate over the bindings currently in scope.
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Scheme)> {
+        self.bindings.iter()
+    }
+}
+
+/// An error raised while inferring a program's types.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InferError {
+    /// Two constructors could not be unified.
+    Mismatch(Type, Type),
+    /// Binding `u32` to the type would make it infinite (occurs-check failure).
+    Occurs(u32, Type),
+    /// A reference to a name that is not in scope.
+    Unbound(String),
+}
+
+/// An algorithm-W style inference engine: a running substitution plus a
+/// fresh-variable counter.
+#[derive(Debug, Default, Clone)]
+pub struct Infer {
+    subst: Subst,
+    counter: u32,
+}
+
+impl Infer {
+    /// Mint a previously-unused type variable.
+    fn fresh(&mut self) -> Type {
+        let var = Type::Var(self.counter);
+        self.counter += 1;
+        var
+    }
+
+    /// Resolve a type through the current substitution, recursing into
+    /// component types.
+    pub fn apply(&self, ty: &Type) -> Type {
+        match ty {
+            Type::Var(n) => match self.subst.get(n) {
+                Some(t) => self.apply(t),
+                None => ty.clone(),
+            },
+            Type::List(ts) => Type::List(ts.iter().map(|t| self.apply(t)).collect()),
+            Type::Map(ks, vs) => Type::Map(
+                ks.iter().map(|t| self.apply(t)).collect(),
+                vs.iter().map(|t| self.apply(t)).collect(),
+            ),
+            Type::Fn(params, ret) => Type::Fn(
+                params.iter().map(|t| self.apply(t)).collect(),
+                Box::new(self.apply(ret)),
+            ),
+            _ => ty.clone(),
+        }
+    }
+
+    /// Unify two types, extending the substitution so that they become equal.
+    pub fn unify(&mut self, a: &Type, b: &Type) -> Result<(), InferError> {
+        let a = self.apply(a);
+        let b = self.apply(b);
+        if a == b {
+            return Ok(());
+        }
+        match (a, b) {
+            (Type::Var(n), other) | (other, Type::Var(n)) => self.bind(n, &other),
+            (Type::List(a), Type::List(b)) => self.unify_many(&a, &b),
+            (Type::Map(ak, av), Type::Map(bk, bv)) => {
+                self.unify_many(&ak, &bk)?;
+                self.unify_many(&av, &bv)
+            }
+            (Type::Fn(ap, ar), Type::Fn(bp, br)) => {
+                self.unify_many(&ap, &bp)?;
+                self.unify(&ar, &br)
+            }
+            (a, b) => Err(InferError::Mismatch(a, b)),
+        }
+    }
+
+    fn unify_many(&mut self, a: &[Type], b: &[Type]) -> Result<(), InferError> {
+        if a.len() != b.len() {
+            return Err(InferError::Mismatch(
+                Type::List(a.to_vec()),
+                Type::List(b.to_vec()),
+            ));
+        }
+        for (a, b) in a.iter().zip(b) {
+            self.unify(a, b)?;
+        }
+        Ok(())
+    }
+
+    /// Bind variable `n` to `ty` after an occurs-check.
+    fn bind(&mut self, n: u32, ty: &Type) -> Result<(), InferError> {
+        if *ty == Type::Var(n) {
+            return Ok(());
+        }
+        if occurs(n, ty) {
+            return Err(InferError::Occurs(n, ty.clone()));
+        }
+        self.subst.insert(n, ty.clone());
+        Ok(())
+    }
+
+    /// Instantiate a scheme by replacing each quantified variable with a fresh
+    /// one.
+    fn instantiate(&mut self, scheme: &Scheme) -> Type {
+        let mut mapping = HashMap::new();
+        for v in &scheme.vars {
+            let fresh = self.fresh();
+            mapping.insert(*v, fresh);
+        }
+        substitute_vars(&scheme.ty, &mapping)
+    }
+
+    /// Generalize a type over the variables free in it but not free in `env`.
+    fn generalize(&self, env: &Env, ty: &Type) -> Scheme {
+        let ty = self.apply(ty);
+        let mut free = HashSet::new();
+        free_type_vars(&ty, &mut free);
+        let env_free = self.env_free_vars(env);
+        let mut vars: Vec<u32> = free.difference(&env_free).copied().collect();
+        vars.sort_unstable();
+        Scheme { vars, ty }
+    }
+
+    fn env_free_vars(&self, env: &Env) -> HashSet<u32> {
+        let mut acc = HashSet::new();
+        for scheme in env.bindings.values() {
+            let mut free = HashSet::new();
+            free_type_vars(&self.apply(&scheme.ty), &mut free);
+            for quantified in &scheme.vars {
+                free.remove(quantified);
+            }
+            acc.extend(free);
+        }
+        acc
+    }
+
+    /// Infer the type of `expr`, threading new bindings into `env`.
+    pub fn infer(&mut self, env: &mut Env, expr: &Expr) -> Result<Type, InferError> {
+        match expr {
+            Expr::Value(value) => Ok(value.type_of()),
+            Expr::Ref(name) => match env.get(name) {
+                Some(scheme) => Ok(self.instantiate(&scheme.clone())),
+                None => Err(InferError::Unbound(name.clone())),
+            },
+            Expr::Var(name, _, value) => {
+                let ty = self.infer(env, value)?;
+                let ty = self.apply(&ty);
+                let scheme = self.generalize(env, &ty);
+                env.insert(name.clone(), scheme);
+                Ok(ty)
+            }
+            Expr::If(cond, then, otherwise) => {
+                let cond = self.infer(env, cond)?;
+                self.unify(&cond, &Type::Bool)?;
+                // Both branches must unify to one common type; branches of
+                // different types are a `Mismatch` here, whereas `type_of`
+                // reports their union.
+                let then = self.infer(env, then)?;
+                let otherwise = self.infer(env, otherwise)?;
+                self.unify(&then, &otherwise)?;
+                Ok(self.apply(&then))
+            }
+            Expr::Lam { params, body } => {
+                let mut env = env.clone();
+                let mut param_types = vec![];
+                for (name, annotation) in params {
+                    let ty = match annotation.first() {
+                        Some(ty) => ty.clone(),
+                        None => self.fresh(),
+                    };
+                    env.insert(
+                        name.clone(),
+                        Scheme {
+                            vars: vec![],
+                            ty: ty.clone(),
+                        },
+                    );
+                    param_types.push(ty);
+                }
+                let ret = self.infer(&mut env, body)?;
+                Ok(self.apply(&Type::Fn(param_types, Box::new(ret))))
+            }
+            Expr::Call { callee, args } => {
+                let callee = self.infer(env, callee)?;
+                let mut arg_types = vec![];
+                for arg in args {
+                    arg_types.push(self.infer(env, arg)?);
+                }
+                let ret = self.fresh();
+                self.unify(&callee, &Type::Fn(arg_types, Box::new(ret.clone())))?;
+                Ok(self.apply(&ret))
+            }
+            Expr::BinOp { op, lhs, rhs } => {
+                let lhs = self.infer(env, lhs)?;
+                let rhs = self.infer(env, rhs)?;
+                self.unify(&lhs, &rhs)?;
+                match op {
+                    Op::Eq | Op::Lt => Ok(Type::Bool),
+                    Op::Sub | Op::Mul => {
+                        self.unify(&lhs, &Type::Integer)?;
+                        Ok(Type::Integer)
+                    }
+                    // `+` is defined over integers and strings; an unresolved
+                    // operand defaults to integer, anything else is rejected.
+                    Op::Add => match self.apply(&lhs) {
+                        Type::String => Ok(Type::String),
+                        Type::Integer => Ok(Type::Integer),
+                        Type::Var(_) => {
+                            self.unify(&lhs, &Type::Integer)?;
+                            Ok(Type::Integer)
+                        }
+                        other => Err(InferError::Mismatch(other, Type::Integer)),
+                    },
+                }
             }
         }
     }
 }
 
+/// Whether variable `n` appears anywhere inside `ty`.
+fn occurs(n: u32, ty: &Type) -> bool {
+    match ty {
+        Type::Var(m) => *m == n,
+        Type::List(ts) => ts.iter().any(|t| occurs(n, t)),
+        Type::Map(ks, vs) => ks.iter().chain(vs).any(|t| occurs(n, t)),
+        Type::Fn(params, ret) => params.iter().any(|t| occurs(n, t)) || occurs(n, ret),
+        _ => false,
+    }
+}
+
+/// Collect the free unification variables of `ty` into `acc`.
+fn free_type_vars(ty: &Type, acc: &mut HashSet<u32>) {
+    match ty {
+        Type::Var(n) => {
+            acc.insert(*n);
+        }
+        Type::List(ts) => ts.iter().for_each(|t| free_type_vars(t, acc)),
+        Type::Map(ks, vs) => ks.iter().chain(vs).for_each(|t| free_type_vars(t, acc)),
+        Type::Fn(params, ret) => {
+            params.iter().for_each(|t| free_type_vars(t, acc));
+            free_type_vars(ret, acc);
+        }
+        _ => {}
+    }
+}
+
+/// Replace every `Type::Var` in `ty` according to `mapping`, leaving others.
+fn substitute_vars(ty: &Type, mapping: &HashMap<u32, Type>) -> Type {
+    match ty {
+        Type::Var(n) => mapping.get(n).cloned().unwrap_or_else(|| ty.clone()),
+        Type::List(ts) => Type::List(ts.iter().map(|t| substitute_vars(t, mapping)).collect()),
+        Type::Map(ks, vs) => Type::Map(
+            ks.iter().map(|t| substitute_vars(t, mapping)).collect(),
+            vs.iter().map(|t| substitute_vars(t, mapping)).collect(),
+        ),
+        Type::Fn(params, ret) => Type::Fn(
+            params.iter().map(|t| substitute_vars(t, mapping)).collect(),
+            Box::new(substitute_vars(ret, mapping)),
+        ),
+        _ => ty.clone(),
+    }
+}
+
+/// Infer a type for every top-level expression, carrying an `Env` across
+/// declarations so later expressions can reference earlier names. Each reported
+/// type has the final substitution applied.
+pub fn infer_program(exprs: &[Expr]) -> Result<Vec<Type>, InferError> {
+    let mut infer = Infer::default();
+    let mut env = Env::default();
+    let mut types = vec![];
+    for expr in exprs {
+        let ty = infer.infer(&mut env, expr)?;
+        types.push(infer.apply(&ty));
+    }
+    Ok(types)
+}
+
+/// A parse failure, carrying the position it occurred at, what the parser
+/// expected, and the character actually found (if any).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub index: usize,
+    pub line: usize,
+    pub col: usize,
+    pub expected: Vec<String>,
+    pub found: Option<char>,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let expected = self
+            .expected
+            .iter()
+            .map(|e| format!("'{e}'"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        match self.found {
+            Some(c) => write!(f, "{}:{}: expected {expected} , found '{c}'", self.line, self.col),
+            None => write!(
+                f,
+                "{}:{}: expected {expected} , found end of input",
+                self.line, self.col
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
 #[derive(Debug, Clone)]
 pub struct Parser {
     body: Vec<char>,
@@ -197,14 +729,58 @@ impl Parser {
         }
     }
 
-    pub fn parse(&mut self) -> Vec<Expr> {
+    /// The 1-based line and column of the current cursor.
+    fn position(&self) -> (usize, usize) {
+        let mut line = 1;
+        let mut col = 1;
+        for &c in &self.body[..self.index.min(self.body.len())] {
+            if c == '\n' {
+                line += 1;
+                col = 1;
+            } else {
+                col += 1;
+            }
+        }
+        (line, col)
+    }
+
+    /// Build an error at the current cursor, expecting one of `expected`.
+    fn error<T>(&self, expected: &[&str]) -> Result<T, ParseError> {
+        let (line, col) = self.position();
+        Err(ParseError {
+            index: self.index,
+            line,
+            col,
+            expected: expected.iter().map(|e| e.to_string()).collect(),
+            found: self.curr_char(),
+        })
+    }
+
+    /// Consume `c` or fail with a positioned error.
+    fn expect(&mut self, c: char) -> Result<(), ParseError> {
+        if self.consume_char(c) {
+            return Ok(());
+        }
+        let (line, col) = self.position();
+        Err(ParseError {
+            index: self.index,
+            line,
+            col,
+            expected: vec![c.to_string()],
+            found: self.curr_char(),
+        })
+    }
+
+    pub fn parse(&mut self) -> Result<Vec<Expr>, ParseError> {
         let mut exprs: Vec<Expr> = vec![];
         self.skip_whitespace();
         while self.is_expr() {
             if self.is_var_dec() {
-                exprs.push(self.consume_var());
-            } else if self.is_value() {
-                exprs.push(self.consume_value().into());
+                exprs.push(self.consume_var()?);
+            } else if self.is_if() {
+                exprs.push(self.consume_if()?);
+            } else {
+                exprs.push(self.consume_expr()?);
             }
             self.skip_whitespace();
             self.consume_char(';');
@@ -212,13 +788,56 @@ impl Parser {
         }
         self.skip_whitespace();
         if !self.sanity_check() {
-            panic!("Did not parse all the way to the end");
+            return self.error(&["end of input"]);
         }
-        exprs
+        Ok(exprs)
     }
 
     fn is_expr(&self) -> bool {
-        self.is_var_dec() || self.is_value()
+        self.is_var_dec()
+            || self.is_if()
+            || self.is_lambda()
+            || self.is_value()
+            || self.is_name_start()
+    }
+
+    fn is_if(&self) -> bool {
+        self.peek(2) == Some(&['i', 'f'])
+            && self
+                .body
+                .get(self.index + 2)
+                .is_some_and(|c| c.is_ascii_whitespace())
+    }
+
+    fn consume_if(&mut self) -> Result<Expr, ParseError> {
+        self.skip(2);
+        self.skip_whitespace();
+        // The condition is an arbitrary expression; the inference engine
+        // verifies it is `Type::Bool`.
+        let cond = self.consume_expr()?;
+        let then = self.consume_branch()?;
+        if self.peek(4) == Some(&['e', 'l', 's', 'e']) {
+            self.skip(4);
+        } else {
+            return self.error(&["else"]);
+        }
+        let otherwise = self.consume_branch()?;
+        Ok(Expr::If(
+            Box::new(cond),
+            Box::new(then),
+            Box::new(otherwise),
+        ))
+    }
+
+    fn consume_branch(&mut self) -> Result<Expr, ParseError> {
+        self.skip_whitespace();
+        self.expect('{')?;
+        self.skip_whitespace();
+        let expr = self.consume_expr()?;
+        self.skip_whitespace();
+        self.expect('}')?;
+        self.skip_whitespace();
+        Ok(expr)
     }
 
     fn sanity_check(&self) -> bool {
@@ -233,7 +852,7 @@ impl Parser {
         self.curr_char() == Some(':')
     }
 
-    fn consume_type_decl(&mut self) -> Vec<Type> {
+    fn consume_type_decl(&mut self) -> Result<Vec<Type>, ParseError> {
         let mut hashset = HashSet::new();
         self.consume_char(':');
         self.skip_whitespace();
@@ -245,11 +864,11 @@ impl Parser {
             } else if self.is_str_type() {
                 self.consume_str_type()
             } else if self.is_list_type() {
-                self.consume_list_type()
+                self.consume_list_type()?
             } else if self.is_map_type() {
-                self.consume_map_type()
+                self.consume_map_type()?
             } else {
-                panic!("Could not parse type");
+                return self.error(&["i64", "bool", "str", "list", "map"]);
             };
             hashset.insert(t);
             self.skip_whitespace();
@@ -260,7 +879,7 @@ impl Parser {
         }
         let mut types: Vec<Type> = hashset.into_iter().collect();
         types.sort();
-        types
+        Ok(types)
     }
 
     fn is_int_type(&self) -> bool {
@@ -294,40 +913,40 @@ impl Parser {
         self.peek(4) == Some(&['l', 'i', 's', 't'])
     }
 
-    fn consume_list_type(&mut self) -> Type {
+    fn consume_list_type(&mut self) -> Result<Type, ParseError> {
         for c in ['l', 'i', 's', 't'] {
             self.consume_char(c);
         }
         self.skip_whitespace();
-        self.consume_char('[');
+        self.expect('[')?;
         self.skip_whitespace();
-        let types = self.consume_type_decl();
+        let types = self.consume_type_decl()?;
         self.skip_whitespace();
-        self.consume_char(']');
+        self.expect(']')?;
         self.skip_whitespace();
-        Type::List(types)
+        Ok(Type::List(types))
     }
 
     fn is_map_type(&self) -> bool {
         self.peek(3) == Some(&['m', 'a', 'p'])
     }
 
-    fn consume_map_type(&mut self) -> Type {
+    fn consume_map_type(&mut self) -> Result<Type, ParseError> {
         for c in ['m', 'a', 'p'] {
             self.consume_char(c);
         }
         self.skip_whitespace();
-        self.consume_char('[');
+        self.expect('[')?;
         self.skip_whitespace();
-        let key_types = self.consume_type_decl();
+        let key_types = self.consume_type_decl()?;
         self.skip_whitespace();
-        self.consume_char(',');
+        self.expect(',')?;
         self.skip_whitespace();
-        let val_types = self.consume_type_decl();
+        let val_types = self.consume_type_decl()?;
         self.skip_whitespace();
-        self.consume_char(']');
+        self.expect(']')?;
         self.skip_whitespace();
-        Type::Map(key_types, val_types)
+        Ok(Type::Map(key_types, val_types))
     }
 
     fn is_true(&self) -> bool {
@@ -352,24 +971,24 @@ impl Parser {
         self.curr_char() == Some('[')
     }
 
-    fn consume_list(&mut self) -> Value {
+    fn consume_list(&mut self) -> Result<Value, ParseError> {
         let mut values = vec![];
-        self.consume_char('[');
+        self.expect('[')?;
         while self.curr_char() != Some(']') {
             self.skip_whitespace();
-            values.push(self.consume_value());
+            values.push(self.consume_value()?);
             self.skip_whitespace();
             self.consume_char(',');
             self.skip_whitespace();
         }
 
-        self.consume_char(']');
+        self.expect(']')?;
 
-        Value::from(values)
+        Ok(Value::from(values))
     }
 
     fn peek(&self, offset: usize) -> Option<&[char]> {
-        if self.is_in_bounds(offset) {
+        if self.index + offset <= self.body.len() {
             Some(&self.body[self.index..self.index + offset])
         } else {
             None
@@ -388,16 +1007,21 @@ impl Parser {
         self.curr_char() == Some('"')
     }
 
-    fn consume_string(&mut self) -> Value {
+    fn consume_string(&mut self) -> Result<Value, ParseError> {
         let mut s = String::new();
-        self.consume_char('"');
+        self.expect('"')?;
         while !self.is_char('"') {
-            s.push(self.curr_char().unwrap());
-            self.next();
+            match self.curr_char() {
+                Some(c) => {
+                    s.push(c);
+                    self.next();
+                }
+                None => return self.error(&["\""]),
+            }
         }
-        self.consume_char('"');
+        self.expect('"')?;
         self.consume_char(';');
-        Value::from(s)
+        Ok(Value::from(s))
     }
 
     fn skip(&mut self, offset: usize) {
@@ -412,13 +1036,13 @@ impl Parser {
         }
     }
 
-    fn consume_bool(&mut self) -> Value {
+    fn consume_bool(&mut self) -> Result<Value, ParseError> {
         if self.is_true() {
-            self.consume_true()
+            Ok(self.consume_true())
         } else if self.is_false() {
-            self.consume_false()
+            Ok(self.consume_false())
         } else {
-            panic!("could not consume bool");
+            self.error(&["true", "false"])
         }
     }
 
@@ -441,50 +1065,208 @@ impl Parser {
         }
     }
 
-    fn consume_var(&mut self) -> Expr {
+    fn consume_var(&mut self) -> Result<Expr, ParseError> {
         self.consume_var_dec();
         self.skip_whitespace();
         let name = self.consume_name();
         self.skip_whitespace();
         let mut types = vec![];
         if self.is_type_decl() {
-            types = self.consume_type_decl();
+            types = self.consume_type_decl()?;
         }
-        self.consume_char('=');
+        self.expect('=')?;
         self.skip_whitespace();
-        let value = self.consume_value();
+        let value = self.consume_expr()?;
         if types.is_empty() {
-            types.push(value.type_of())
+            types = value.type_of();
         }
         self.skip_whitespace();
         self.consume_char(';');
-        Expr::Var(name, types, Box::new(Expr::from(value)))
+        Ok(Expr::Var(name, types, Box::new(value)))
+    }
+
+    fn consume_expr(&mut self) -> Result<Expr, ParseError> {
+        self.consume_binop(0)
+    }
+
+    /// Precedence-climbing parse of infix operators.
+    fn consume_binop(&mut self, min_prec: u8) -> Result<Expr, ParseError> {
+        let mut lhs = self.consume_postfix()?;
+        loop {
+            self.skip_whitespace();
+            let op = match self.peek_op() {
+                Some(op) => op,
+                None => break,
+            };
+            let prec = op_prec(&op);
+            if prec < min_prec {
+                break;
+            }
+            self.consume_op(&op);
+            self.skip_whitespace();
+            let rhs = self.consume_binop(prec + 1)?;
+            lhs = Expr::BinOp {
+                op,
+                lhs: Box::new(lhs),
+                rhs: Box::new(rhs),
+            };
+        }
+        Ok(lhs)
     }
 
-    fn consume_map_entry(&mut self) -> (Value, Value) {
+    /// A primary expression followed by zero or more call applications.
+    fn consume_postfix(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.consume_primary()?;
+        loop {
+            self.skip_whitespace();
+            if self.is_char('(') {
+                let args = self.consume_args()?;
+                expr = Expr::Call {
+                    callee: Box::new(expr),
+                    args,
+                };
+            } else {
+                break;
+            }
+        }
+        Ok(expr)
+    }
+
+    fn consume_primary(&mut self) -> Result<Expr, ParseError> {
         self.skip_whitespace();
-        let key = self.consume_value();
+        if self.is_lambda() {
+            self.consume_lambda()
+        } else if self.is_char('(') {
+            self.expect('(')?;
+            self.skip_whitespace();
+            let expr = self.consume_expr()?;
+            self.skip_whitespace();
+            self.expect(')')?;
+            Ok(expr)
+        } else if self.is_if() {
+            self.consume_if()
+        } else if self.is_value() {
+            Ok(Expr::from(self.consume_value()?))
+        } else if self.is_name_start() {
+            Ok(Expr::Ref(self.consume_name()))
+        } else {
+            self.error(&["expression"])
+        }
+    }
+
+    fn consume_args(&mut self) -> Result<Vec<Expr>, ParseError> {
+        self.expect('(')?;
         self.skip_whitespace();
-        self.consume_char(':');
+        let mut args = vec![];
+        while !self.is_char(')') {
+            args.push(self.consume_expr()?);
+            self.skip_whitespace();
+            self.consume_char(',');
+            self.skip_whitespace();
+        }
+        self.expect(')')?;
+        Ok(args)
+    }
+
+    fn is_lambda(&self) -> bool {
+        self.peek(2) == Some(&['f', 'n'])
+            && self
+                .body
+                .get(self.index + 2)
+                .is_some_and(|c| *c == '(' || c.is_ascii_whitespace())
+    }
+
+    fn consume_lambda(&mut self) -> Result<Expr, ParseError> {
+        self.skip(2);
+        self.skip_whitespace();
+        self.expect('(')?;
         self.skip_whitespace();
-        let val = self.consume_value();
+        let mut params = vec![];
+        while !self.is_char(')') {
+            let name = self.consume_name();
+            if name.is_empty() {
+                return self.error(&["parameter name", ")"]);
+            }
+            self.skip_whitespace();
+            let types = if self.is_type_decl() {
+                self.consume_type_decl()?
+            } else {
+                vec![]
+            };
+            params.push((name, types));
+            self.skip_whitespace();
+            self.consume_char(',');
+            self.skip_whitespace();
+        }
+        self.expect(')')?;
         self.skip_whitespace();
-        (key, val)
+        // An optional `-> <type>` return annotation; the body's inferred type is
+        // authoritative, so the annotation is parsed and discarded.
+        if self.peek(2) == Some(&['-', '>']) {
+            self.skip(2);
+            self.skip_whitespace();
+            self.consume_type_decl()?;
+        }
+        self.skip_whitespace();
+        self.expect('{')?;
+        self.skip_whitespace();
+        let body = self.consume_expr()?;
+        self.skip_whitespace();
+        self.expect('}')?;
+        Ok(Expr::Lam {
+            params,
+            body: Box::new(body),
+        })
     }
 
-    fn consume_map(&mut self) -> Value {
-        self.consume_char('{');
+    fn is_name_start(&self) -> bool {
+        self.curr_char().is_some_and(|c| c.is_ascii_alphabetic())
+    }
+
+    fn peek_op(&self) -> Option<Op> {
+        if self.peek(2) == Some(&['=', '=']) {
+            return Some(Op::Eq);
+        }
+        match self.curr_char() {
+            Some('+') => Some(Op::Add),
+            Some('-') if self.body.get(self.index + 1) != Some(&'>') => Some(Op::Sub),
+            Some('*') => Some(Op::Mul),
+            Some('<') => Some(Op::Lt),
+            _ => None,
+        }
+    }
+
+    fn consume_op(&mut self, op: &Op) {
+        match op {
+            Op::Eq => self.skip(2),
+            _ => self.skip(1),
+        }
+    }
+
+    fn consume_map_entry(&mut self) -> Result<(Value, Value), ParseError> {
+        self.skip_whitespace();
+        let key = self.consume_value()?;
+        self.skip_whitespace();
+        self.expect(':')?;
+        self.skip_whitespace();
+        let val = self.consume_value()?;
+        self.skip_whitespace();
+        Ok((key, val))
+    }
+
+    fn consume_map(&mut self) -> Result<Value, ParseError> {
+        self.expect('{')?;
         self.skip_whitespace();
         let mut hashmap = HashMap::new();
         while self.is_value() && self.curr_char() != Some('}') {
-            let (key, val) = self.consume_map_entry();
+            let (key, val) = self.consume_map_entry()?;
             hashmap.insert(key, val);
             self.consume_char(',');
             self.skip_whitespace();
         }
-        self.consume_char('}');
+        self.expect('}')?;
         self.skip_whitespace();
-        Value::from(hashmap)
+        Ok(Value::from(hashmap))
     }
 
     fn is_var_dec(&self) -> bool {
@@ -499,9 +1281,9 @@ impl Parser {
         self.is_integer() || self.is_string() || self.is_bool() || self.is_list() || self.is_map()
     }
 
-    fn consume_value(&mut self) -> Value {
+    fn consume_value(&mut self) -> Result<Value, ParseError> {
         if self.is_integer() {
-            self.consume_integer()
+            Ok(self.consume_integer())
         } else if self.is_string() {
             self.consume_string()
         } else if self.is_bool() {
@@ -511,7 +1293,7 @@ impl Parser {
         } else if self.is_map() {
             self.consume_map()
         } else {
-            panic!("Could not consume value");
+            self.error(&["value"])
         }
     }
 
@@ -553,7 +1335,7 @@ mod tests {
 
     fn test(input: &str, expected: Vec<Expr>) {
         let mut parser = Parser::new(input);
-        assert_eq!(parser.parse(), expected);
+        assert_eq!(parser.parse().unwrap(), expected);
     }
 
     fn test_types(input: &str, expected: Vec<Type>) {
@@ -561,6 +1343,7 @@ mod tests {
         assert_eq!(
             parser
                 .parse()
+                .unwrap()
                 .into_iter()
                 .flat_map(|x| x.type_of())
                 .collect::<Vec<Type>>(),
@@ -568,6 +1351,16 @@ mod tests {
         );
     }
 
+    fn check_input(input: &str) -> Result<Vec<Expr>, Vec<TypeError>> {
+        let mut parser = Parser::new(input);
+        check(parser.parse().unwrap())
+    }
+
+    fn infer_source(input: &str) -> Result<Vec<Type>, InferError> {
+        let mut parser = Parser::new(input);
+        infer_program(&parser.parse().unwrap())
+    }
+
     #[test]
     fn parse_int_var() {
         let input = "let x = 10;";
@@ -723,6 +1516,265 @@ mod tests {
         );
     }
 
+    #[test]
+    fn check_accepts_conforming_scalar() {
+        let input = "let x: bool | str = false;";
+        assert!(check_input(input).is_ok());
+    }
+
+    #[test]
+    fn check_rejects_nonconforming_scalar() {
+        let input = "let x: i64 = \"oops\";";
+        assert_eq!(
+            check_input(input),
+            Err(vec![TypeError {
+                name: "x".to_string(),
+                found: Type::String,
+                expected: vec![Type::Integer],
+            }])
+        );
+    }
+
+    #[test]
+    fn check_accepts_conforming_list() {
+        let input = "let x: list[i64 | str] = [1, \"a\"];";
+        assert!(check_input(input).is_ok());
+    }
+
+    #[test]
+    fn check_rejects_nonconforming_list_element() {
+        let input = "let x: list[i64] = [1, false];";
+        assert_eq!(
+            check_input(input),
+            Err(vec![TypeError {
+                name: "x".to_string(),
+                found: Type::List(vec![Type::Bool, Type::Integer]),
+                expected: vec![Type::List(vec![Type::Integer])],
+            }])
+        );
+    }
+
+    #[test]
+    fn check_rejects_nonconforming_map_value() {
+        let input = "let x: map[i64, str] = {1: false};";
+        assert_eq!(
+            check_input(input),
+            Err(vec![TypeError {
+                name: "x".to_string(),
+                found: Type::Map(vec![Type::Integer], vec![Type::Bool]),
+                expected: vec![Type::Map(vec![Type::Integer], vec![Type::String])],
+            }])
+        );
+    }
+
+    #[test]
+    fn parse_if_else() {
+        let input = "if true { 1 } else { \"s\" };";
+        test(
+            input,
+            vec![Expr::If(
+                Box::new(Expr::from(true)),
+                Box::new(Expr::from(1)),
+                Box::new(Expr::from("s".to_string())),
+            )],
+        );
+    }
+
+    #[test]
+    fn parse_if_with_computed_condition() {
+        let input = "if 1 < 2 { 1 } else { 2 };";
+        test(
+            input,
+            vec![Expr::If(
+                Box::new(Expr::BinOp {
+                    op: Op::Lt,
+                    lhs: Box::new(Expr::from(1)),
+                    rhs: Box::new(Expr::from(2)),
+                }),
+                Box::new(Expr::from(1)),
+                Box::new(Expr::from(2)),
+            )],
+        );
+    }
+
+    #[test]
+    fn if_else_unions_branch_types() {
+        let input = "if true { 1 } else { \"s\" };";
+        test_types(input, vec![Type::Integer, Type::String]);
+    }
+
+    #[test]
+    fn if_condition_must_be_bool() {
+        assert_eq!(
+            infer_source("if 1 { 1 } else { 2 };"),
+            Err(InferError::Mismatch(Type::Integer, Type::Bool))
+        );
+    }
+
+    #[test]
+    fn if_requires_else() {
+        let mut parser = Parser::new("if true { 1 };");
+        assert!(parser.parse().is_err());
+    }
+
+    #[test]
+    fn infer_if_unifies_branches() {
+        assert_eq!(
+            infer_source("if true { 1 } else { 2 };"),
+            Ok(vec![Type::Integer])
+        );
+    }
+
+    #[test]
+    fn parse_error_reports_position() {
+        let mut parser = Parser::new("let x = ;");
+        let err = parser.parse().unwrap_err();
+        assert_eq!(err.line, 1);
+        assert_eq!(err.found, Some(';'));
+    }
+
+    #[test]
+    fn parse_error_display_format() {
+        let err = ParseError {
+            index: 4,
+            line: 1,
+            col: 5,
+            expected: vec!["]".to_string()],
+            found: Some(','),
+        };
+        assert_eq!(err.to_string(), "1:5: expected ']' , found ','");
+    }
+
+    #[test]
+    fn parse_lambda() {
+        let input = "fn(a: i64) -> i64 { a };";
+        test(
+            input,
+            vec![Expr::Lam {
+                params: vec![("a".to_string(), vec![Type::Integer])],
+                body: Box::new(Expr::Ref("a".to_string())),
+            }],
+        );
+    }
+
+    #[test]
+    fn parse_call() {
+        let input = "f(1, 2)";
+        test(
+            input,
+            vec![Expr::Call {
+                callee: Box::new(Expr::Ref("f".to_string())),
+                args: vec![Expr::from(1), Expr::from(2)],
+            }],
+        );
+    }
+
+    #[test]
+    fn parse_binop_precedence() {
+        let input = "1 + 2 * 3";
+        test(
+            input,
+            vec![Expr::BinOp {
+                op: Op::Add,
+                lhs: Box::new(Expr::from(1)),
+                rhs: Box::new(Expr::BinOp {
+                    op: Op::Mul,
+                    lhs: Box::new(Expr::from(2)),
+                    rhs: Box::new(Expr::from(3)),
+                }),
+            }],
+        );
+    }
+
+    #[test]
+    fn infer_call_returns_function_result() {
+        let types = infer_source("let id = fn(a: i64) { a }; id(5);").unwrap();
+        assert_eq!(types[1], Type::Integer);
+    }
+
+    #[test]
+    fn infer_binop_integer() {
+        assert_eq!(infer_source("1 + 2 * 3"), Ok(vec![Type::Integer]));
+    }
+
+    #[test]
+    fn infer_binop_equality_is_bool() {
+        assert_eq!(infer_source("1 == 2"), Ok(vec![Type::Bool]));
+    }
+
+    #[test]
+    fn infer_add_concatenates_strings() {
+        assert_eq!(infer_source("\"a\" + \"b\""), Ok(vec![Type::String]));
+    }
+
+    #[test]
+    fn infer_add_rejects_bool_operands() {
+        assert_eq!(
+            infer_source("true + false"),
+            Err(InferError::Mismatch(Type::Bool, Type::Integer))
+        );
+    }
+
+    #[test]
+    fn infer_call_argument_mismatch_errors() {
+        assert_eq!(
+            infer_source("let id = fn(a: i64) { a }; id(\"x\");"),
+            Err(InferError::Mismatch(Type::Integer, Type::String))
+        );
+    }
+
+    #[test]
+    fn infer_reference_uses_earlier_binding() {
+        let program = vec![
+            Expr::Var(
+                "x".to_string(),
+                vec![Type::Integer],
+                Box::new(Expr::from(10)),
+            ),
+            Expr::Ref("x".to_string()),
+        ];
+        assert_eq!(
+            infer_program(&program),
+            Ok(vec![Type::Integer, Type::Integer])
+        );
+    }
+
+    #[test]
+    fn infer_unbound_reference_errors() {
+        let program = vec![Expr::Ref("missing".to_string())];
+        assert_eq!(
+            infer_program(&program),
+            Err(InferError::Unbound("missing".to_string()))
+        );
+    }
+
+    #[test]
+    fn unify_binds_variable_to_concrete() {
+        let mut infer = Infer::default();
+        let var = infer.fresh();
+        assert_eq!(infer.unify(&var, &Type::Integer), Ok(()));
+        assert_eq!(infer.apply(&var), Type::Integer);
+    }
+
+    #[test]
+    fn unify_reports_mismatch() {
+        let mut infer = Infer::default();
+        assert_eq!(
+            infer.unify(&Type::Integer, &Type::String),
+            Err(InferError::Mismatch(Type::Integer, Type::String))
+        );
+    }
+
+    #[test]
+    fn unify_occurs_check_fails() {
+        let mut infer = Infer::default();
+        let var = infer.fresh();
+        assert_eq!(
+            infer.unify(&var, &Type::List(vec![var.clone()])),
+            Err(InferError::Occurs(0, Type::List(vec![Type::Var(0)])))
+        );
+    }
+
     #[test]
     fn mixed_type_list() {
         let input = "[true, false, \"hello\", 1, { 1: 2, true: [1, true , \"str\"] }, [3]];";
@@ -2,7 +2,19 @@ use type_inference::*;
 
 fn main() {
     let input = "let x: bool | str = false;";
-    let mut parser = Parser::new(input);
-    parser.parse();
-    dbg!(parser);
+    let exprs = match Parser::new(input).parse() {
+        Ok(exprs) => exprs,
+        Err(err) => {
+            eprintln!("{err}");
+            return;
+        }
+    };
+    match infer_program(&exprs) {
+        Ok(types) => {
+            for ty in types {
+                println!("{ty}");
+            }
+        }
+        Err(err) => eprintln!("{err:?}"),
+    }
 }